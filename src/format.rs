@@ -1,3 +1,4 @@
+use std::ascii::AsciiExt;
 use std::fmt::Display;
 use std::num::Int;
 use std::old_io::IoResult;
@@ -16,8 +17,10 @@ pub enum Field<'a> {
     YearOfCentury(NumArguments),
 
     MonthName(bool, TextArguments),
+    MonthNumber(NumArguments),
 
     Day(NumArguments),
+    DayOfYear(NumArguments),
     WeekdayName(bool, TextArguments),
 }
 
@@ -29,11 +32,80 @@ impl<'a> Field<'a> {
             Field::YearOfCentury(a)       => a.format(w, when.year_of_century()),
             Field::MonthName(true, a)     => a.format(w, long_month_name(when.month())),
             Field::MonthName(false, a)    => a.format(w, short_month_name(when.month())),
+            Field::MonthNumber(a)         => a.format(w, month_number(when.month())),
             Field::Day(a)                 => a.format(w, when.day()),
+            Field::DayOfYear(a)           => a.format(w, when.yearday()),
             Field::WeekdayName(true, a)   => a.format(w, long_day_name(when.weekday())),
             Field::WeekdayName(false, a)  => a.format(w, short_day_name(when.weekday())),
         }
     }
+
+    /// Reads this field out of the input string at the given cursor
+    /// position, advancing the cursor past whatever was consumed and
+    /// stashing the result in `parsed`.
+    ///
+    /// Note that two adjacent numeric fields with no literal separator
+    /// between them (such as `{:Y}{:D}`) are ambiguous: this only ever
+    /// greedily reads as many digits as the first field allows, so the
+    /// second field will consume whatever (if anything) is left over. There
+    /// is no backtracking.
+    fn parse(self, input: &str, cursor: usize, parsed: &mut Parsed) -> Result<usize, ParseError<'a>> {
+        match self {
+            Field::Literal(s) => {
+                if input[cursor..].starts_with(s) {
+                    Ok(cursor + s.len())
+                }
+                else {
+                    Err(ParseError::LiteralMismatch { expected: s, pos: cursor })
+                }
+            },
+
+            Field::Year(_) => {
+                let (value, new_cursor) = try!(parse_number(input, cursor, 4));
+                parsed.year = Some(value);
+                Ok(new_cursor)
+            },
+
+            Field::YearOfCentury(_) => {
+                let (value, new_cursor) = try!(parse_number(input, cursor, 2));
+                parsed.year_of_century = Some(value);
+                Ok(new_cursor)
+            },
+
+            Field::Day(_) => {
+                let (value, new_cursor) = try!(parse_number(input, cursor, 2));
+                parsed.day = Some(value);
+                Ok(new_cursor)
+            },
+
+            Field::MonthName(_, _) => {
+                let (index, new_cursor) = try!(match_name(input, cursor, &LONG_MONTH_NAMES, &SHORT_MONTH_NAMES));
+                parsed.month = Some(index);
+                Ok(new_cursor)
+            },
+
+            Field::MonthNumber(_) => {
+                let (value, new_cursor) = try!(parse_number(input, cursor, 2));
+                if value < 1 || value > 12 {
+                    return Err(ParseError::OutOfRange);
+                }
+                parsed.month = Some((value - 1) as usize);
+                Ok(new_cursor)
+            },
+
+            Field::DayOfYear(_) => {
+                let (value, new_cursor) = try!(parse_number(input, cursor, 3));
+                parsed.day_of_year = Some(value);
+                Ok(new_cursor)
+            },
+
+            Field::WeekdayName(_, _) => {
+                let (index, new_cursor) = try!(match_name(input, cursor, &LONG_DAY_NAMES, &SHORT_DAY_NAMES));
+                parsed.weekday = Some(index);
+                Ok(new_cursor)
+            },
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -47,10 +119,70 @@ pub enum FormatError {
     OpenCurlyBrace { open_pos: usize },
     CloseCurlyBrace { close_pos: usize },
     MissingField { open_pos: usize, close_pos: usize },
+
+    /// The text between the `:` and the field letter (the fill/alignment,
+    /// zero-padding flag, and width) didn't follow the `[[fill]align][0][width]`
+    /// grammar.
+    InvalidFormatSpec { pos: usize },
+
+    /// The strftime string asked for a specifier (such as `%H`, `%M`, `%S`,
+    /// or `%p`) that reads or writes a time of day. This crate's `Field`
+    /// type doesn't have anywhere to put that yet, because there's no
+    /// time-of-day type in scope for `DateFormat` to work with.
+    UnsupportedField { specifier: char, pos: usize },
 }
 
 impl Copy for FormatError { }
 
+/// An error that can occur trying to read a date out of a string, using a
+/// `DateFormat` as the pattern to match against.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParseError<'a> {
+
+    /// A literal piece of the format string (such as the `-` in `{:Y}-{:M}`)
+    /// was not found at the expected position in the input.
+    LiteralMismatch { expected: &'a str, pos: usize },
+
+    /// A numeric field (year, year-of-century, month number, day, or
+    /// day-of-year) was expected at this position, but no digits were there
+    /// to read.
+    InvalidDigit { pos: usize },
+
+    /// A month or weekday name was expected at this position, but none of
+    /// the long or short names matched the input.
+    UnknownName { pos: usize },
+
+    /// The fields that were read don't contain enough information to build
+    /// a complete date (for example, there was no day field at all).
+    MissingField,
+
+    /// Two of the fields that were read disagree with each other, such as
+    /// a year and a year-of-century that don't share the same last two
+    /// digits, or a weekday that doesn't match the actual date.
+    InconsistentFields,
+
+    /// The fields were read successfully, but they don't describe a date
+    /// that actually exists.
+    OutOfRange,
+}
+
+impl<'a> Copy for ParseError<'a> { }
+
+/// The pieces of a date that have been read out of an input string so far.
+/// Each field starts as `None`, and is filled in as the corresponding
+/// `Field` is matched against the input. This is deliberately loose: it's
+/// only once every field in the format has been consumed that the date is
+/// checked for completeness and internal consistency.
+#[derive(Default, Debug)]
+struct Parsed {
+    year:            Option<i64>,
+    year_of_century: Option<i64>,
+    month:           Option<usize>,
+    day:             Option<i64>,
+    day_of_year:     Option<i64>,
+    weekday:         Option<usize>,
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub struct Arguments {
     alignment: Option<Alignment>,
@@ -94,8 +226,23 @@ impl TextArguments {
 pub struct NumArguments { args: Arguments }
 
 impl NumArguments {
+    /// Formats `number`, zero-padding (or otherwise aligning) its digits
+    /// rather than the whole thing, so that a negative number pads out to
+    /// `-005` instead of `00-5`.
     fn format<N: Int + Display>(self, w: &mut Vec<u8>, number: N) -> IoResult<()> {
-        self.args.format(w, &number.to_string())
+        let s = number.to_string();
+
+        if s.starts_with('-') {
+            let digits = &s[1..];
+            let width  = self.args.width.map(|width| if width > 0 { width - 1 } else { 0 });
+            let inner_args = Arguments { width: width, .. self.args };
+
+            try!(w.write_str("-"));
+            inner_args.format(w, digits)
+        }
+        else {
+            self.args.format(w, &s)
+        }
     }
 }
 
@@ -118,6 +265,133 @@ impl<'a> DateFormat<'a> {
 
         Ok(DateFormat { fields: parser.fields })
     }
+
+    /// Reads a date out of the given input string, by walking this format's
+    /// fields over it in order. This is the inverse of `format`: literal
+    /// fields must match the input exactly, while numeric and name fields
+    /// accumulate into a `LocalDate` once every field has been consumed.
+    pub fn parse_date<'b>(&self, input: &'b str) -> Result<LocalDate, ParseError<'a>> {
+        let mut cursor = 0;
+        let mut parsed = Parsed::default();
+
+        for field in self.fields.iter() {
+            cursor = try!(field.parse(input, cursor, &mut parsed));
+        }
+
+        parsed.into_date()
+    }
+
+    /// Parses a strftime-style format string (`%Y-%m-%d`, `%A, %d %B %Y`,
+    /// and so on) into a `DateFormat`, producing the same `Vec<Field>` that
+    /// the brace syntax does. This is a second front-end for the existing
+    /// `{:Y}`-style syntax; the two can't currently be mixed in one string.
+    ///
+    /// `%H`, `%M`, `%S`, and `%p` are recognised but return
+    /// `FormatError::UnsupportedField`, because this crate has no
+    /// time-of-day type for `Field` to read them from yet.
+    pub fn parse_strftime(input: &'a str) -> Result<DateFormat<'a>, FormatError> {
+        let mut fields = Vec::new();
+        let mut iter = input.char_indices();
+        let mut anchor: Option<usize> = None;
+
+        loop {
+            match iter.next() {
+                Some((pos, '%')) => {
+                    collect_literal(input, &mut anchor, &mut fields, Some(pos));
+
+                    let field = match iter.next() {
+                        Some((_, 'Y')) => Field::Year(NumArguments { args: Arguments::empty() }),
+                        Some((_, 'y')) => Field::YearOfCentury(zero_padded(2)),
+                        Some((_, 'm')) => Field::MonthNumber(zero_padded(2)),
+                        Some((_, 'd')) => Field::Day(zero_padded(2)),
+                        Some((_, 'j')) => Field::DayOfYear(zero_padded(3)),
+                        Some((_, 'A')) => Field::WeekdayName(true, TextArguments { args: Arguments::empty() }),
+                        Some((_, 'a')) => Field::WeekdayName(false, TextArguments { args: Arguments::empty() }),
+                        Some((_, 'B')) => Field::MonthName(true, TextArguments { args: Arguments::empty() }),
+                        Some((_, 'b')) => Field::MonthName(false, TextArguments { args: Arguments::empty() }),
+                        Some((pos, '%')) => Field::Literal(&input[pos .. pos + 1]),
+                        Some((pos, c @ 'H')) | Some((pos, c @ 'M')) | Some((pos, c @ 'S')) | Some((pos, c @ 'p')) =>
+                            return Err(FormatError::UnsupportedField { specifier: c, pos: pos }),
+                        Some((pos, c)) => return Err(FormatError::InvalidChar { c: c, colon: false, pos: pos }),
+                        None           => return Err(FormatError::OpenCurlyBrace { open_pos: pos }),
+                    };
+
+                    fields.push(field);
+                },
+                Some((pos, _)) => {
+                    if anchor.is_none() {
+                        anchor = Some(pos);
+                    }
+                },
+                None => break,
+            }
+        }
+
+        collect_literal(input, &mut anchor, &mut fields, None);
+        Ok(DateFormat { fields: fields })
+    }
+
+    /// The `DateFormat` for RFC 3339's `full-date` production: a
+    /// zero-padded `YYYY-MM-DD`, e.g. `2015-09-13`.
+    pub fn rfc3339() -> DateFormat<'static> {
+        DateFormat { fields: vec![
+            Field::Year(zero_padded(4)),
+            Field::Literal("-"),
+            Field::MonthNumber(zero_padded(2)),
+            Field::Literal("-"),
+            Field::Day(zero_padded(2)),
+        ] }
+    }
+
+    /// The `DateFormat` for RFC 2822's date syntax, e.g. `Wed, 18 Feb 2015`.
+    pub fn rfc2822() -> DateFormat<'static> {
+        DateFormat { fields: vec![
+            Field::WeekdayName(false, TextArguments { args: Arguments::empty() }),
+            Field::Literal(", "),
+            Field::Day(zero_padded(2)),
+            Field::Literal(" "),
+            Field::MonthName(false, TextArguments { args: Arguments::empty() }),
+            Field::Literal(" "),
+            Field::Year(zero_padded(4)),
+        ] }
+    }
+}
+
+impl LocalDate {
+
+    /// Formats this date using RFC 3339's `full-date` syntax, e.g.
+    /// `2015-09-13`.
+    pub fn to_rfc3339(&self) -> String {
+        DateFormat::rfc3339().format(*self)
+    }
+
+    /// Parses a date out of a string in RFC 3339's `full-date` syntax.
+    pub fn parse_from_rfc3339(input: &str) -> Result<LocalDate, ParseError<'static>> {
+        DateFormat::rfc3339().parse_date(input)
+    }
+
+    /// Parses a date out of a string in RFC 2822's date syntax, e.g.
+    /// `Wed, 18 Feb 2015`.
+    pub fn parse_from_rfc2822(input: &str) -> Result<LocalDate, ParseError<'static>> {
+        DateFormat::rfc2822().parse_date(input)
+    }
+}
+
+/// Shared by `parse_strftime`: pushes a pending run of plain text as a
+/// `Field::Literal`, the same way `FormatParser::collect_up_to_anchor` does
+/// for the brace syntax.
+fn collect_literal<'a>(input: &'a str, anchor: &mut Option<usize>, fields: &mut Vec<Field<'a>>, position: Option<usize>) {
+    if let Some(pos) = anchor.take() {
+        let text = match position {
+            Some(new_pos) => &input[pos..new_pos],
+            None           => &input[pos..],
+        };
+        fields.push(Field::Literal(text));
+    }
+}
+
+fn zero_padded(width: usize) -> NumArguments {
+    NumArguments { args: Arguments { alignment: Some(Alignment::Right), width: Some(width), pad_char: Some('0') } }
 }
 
 struct FormatParser<'a> {
@@ -199,7 +473,6 @@ impl<'a> FormatParser<'a> {
     // still use slices.
 
     fn parse_a_thing(&mut self, open_pos: usize) -> Result<Field<'a>, FormatError> {
-        let args = Arguments::empty();
         let mut bit = None;
         let mut close_pos;
         let mut first = true;
@@ -207,15 +480,16 @@ impl<'a> FormatParser<'a> {
         loop {
             match self.next() {
                 Some((pos, '{')) if first => return Ok(Field::Literal(&self.input[pos .. pos + 1])),
-                Some((_, ':')) => {
-                    let bitlet = match self.next() {
-                        Some((_, 'Y')) => Field::Year(NumArguments { args: args }),
-                        Some((_, 'y')) => Field::YearOfCentury(NumArguments { args: args }),
-                        Some((_, 'M')) => Field::MonthName(true, TextArguments { args: args }),
-                        Some((_, 'D')) => Field::Day(NumArguments { args: args }),
-                        Some((_, 'E')) => Field::WeekdayName(true, TextArguments { args: args }),
-                        Some((pos, c)) => return Err(FormatError::InvalidChar { c: c, colon: true, pos: pos }),
-                        None => return Err(FormatError::OpenCurlyBrace { open_pos: open_pos }),
+                Some((colon_pos, ':')) => {
+                    let (args, letter_pos, letter) = try!(self.parse_format_spec(colon_pos));
+
+                    let bitlet = match letter {
+                        'Y' => Field::Year(NumArguments { args: args }),
+                        'y' => Field::YearOfCentury(NumArguments { args: args }),
+                        'M' => Field::MonthName(true, TextArguments { args: args }),
+                        'D' => Field::Day(NumArguments { args: args }),
+                        'E' => Field::WeekdayName(true, TextArguments { args: args }),
+                        c   => return Err(FormatError::InvalidChar { c: c, colon: true, pos: letter_pos }),
                     };
 
                     bit = Some(bitlet);
@@ -233,6 +507,266 @@ impl<'a> FormatParser<'a> {
             None    => Err(FormatError::MissingField { open_pos: open_pos, close_pos: close_pos }),
         }
     }
+
+    // Reads everything between the `:` and the field letter, which can
+    // carry a Rust-format-like spec: an optional fill char plus alignment
+    // (`<`, `^`, `>`), an optional `0` flag for zero-padding, and a decimal
+    // width, e.g. `{:>04Y}` or `{:*^6D}`. Returns the resulting `Arguments`
+    // along with the position and identity of the field letter that ended
+    // the spec. Only the letters `is_field_letter` accepts end a spec; any
+    // other character is read as part of the spec itself, so an unknown
+    // field letter surfaces as `MissingField` or `InvalidFormatSpec`.
+    fn parse_format_spec(&mut self, colon_pos: usize) -> Result<(Arguments, usize, char), FormatError> {
+        let mut spec_chars = Vec::new();
+        let mut letter = None;
+
+        while letter.is_none() {
+            match self.next() {
+                Some((pos, c)) if is_field_letter(c) => letter = Some((pos, c)),
+                Some((pos, '}')) => return Err(FormatError::MissingField { open_pos: colon_pos, close_pos: pos }),
+                Some((pos, c)) => spec_chars.push((pos, c)),
+                None => return Err(FormatError::OpenCurlyBrace { open_pos: colon_pos }),
+            }
+        }
+
+        let (letter_pos, letter_char) = letter.unwrap();
+        let args = try!(parse_spec_chars(&spec_chars));
+        Ok((args, letter_pos, letter_char))
+    }
+}
+
+fn is_field_letter(c: char) -> bool {
+    match c {
+        'Y' | 'y' | 'M' | 'D' | 'E' => true,
+        _                           => false,
+    }
+}
+
+// Parses the `[[fill]align][0][width]` grammar out of the characters
+// collected between the `:` and the field letter.
+fn parse_spec_chars(chars: &[(usize, char)]) -> Result<Arguments, FormatError> {
+    let mut index = 0;
+    let mut alignment = None;
+    let mut pad_char = None;
+
+    if index < chars.len() {
+        let fill_and_align = if index + 1 < chars.len() { alignment_for(chars[index + 1].1) } else { None };
+
+        if let Some(a) = fill_and_align {
+            pad_char  = Some(chars[index].1);
+            alignment = Some(a);
+            index += 2;
+        }
+        else if let Some(a) = alignment_for(chars[index].1) {
+            alignment = Some(a);
+            index += 1;
+        }
+    }
+
+    let zero = index < chars.len() && chars[index].1 == '0';
+    if zero {
+        index += 1;
+    }
+
+    let width_start = index;
+    let mut width_str = String::new();
+    while index < chars.len() && chars[index].1.is_digit(10) {
+        width_str.push(chars[index].1);
+        index += 1;
+    }
+
+    if index != chars.len() {
+        return Err(FormatError::InvalidFormatSpec { pos: chars[index].0 });
+    }
+
+    let width = if width_str.is_empty() {
+        None
+    }
+    else {
+        match width_str.parse() {
+            Ok(width) => Some(width),
+            Err(_)     => return Err(FormatError::InvalidFormatSpec { pos: chars[width_start].0 }),
+        }
+    };
+
+    if zero {
+        if pad_char.is_none()  { pad_char  = Some('0'); }
+        if alignment.is_none() { alignment = Some(Alignment::Right); }
+    }
+
+    Ok(Arguments {
+        alignment: alignment,
+        width:     width,
+        pad_char:  pad_char,
+    })
+}
+
+fn alignment_for(c: char) -> Option<Alignment> {
+    match c {
+        '<' => Some(Alignment::Left),
+        '^' => Some(Alignment::Middle),
+        '>' => Some(Alignment::Right),
+        _   => None,
+    }
+}
+
+impl Parsed {
+
+    /// Turns the fields read so far into a `LocalDate`, checking that
+    /// enough of them were present, and that they don't disagree with each
+    /// other.
+    fn into_date<'a>(self) -> Result<LocalDate, ParseError<'a>> {
+        let year = match (self.year, self.year_of_century) {
+            (Some(year), None)                => year,
+            (None, Some(year_of_century))      => 2000 + year_of_century,
+            (Some(year), Some(year_of_century)) if year % 100 == year_of_century => year,
+            (Some(_), Some(_))                 => return Err(ParseError::InconsistentFields),
+            (None, None)                       => return Err(ParseError::MissingField),
+        };
+
+        let date = match self.day_of_year {
+            Some(day_of_year) => {
+                if self.month.is_some() || self.day.is_some() {
+                    return Err(ParseError::InconsistentFields);
+                }
+
+                match local::Date::yd(year, day_of_year) {
+                    Ok(date) => date,
+                    Err(_)    => return Err(ParseError::OutOfRange),
+                }
+            },
+
+            None => {
+                let month = match self.month {
+                    Some(index) => month_from_index(index),
+                    None         => return Err(ParseError::MissingField),
+                };
+
+                let day = match self.day {
+                    Some(day) => day,
+                    None       => return Err(ParseError::MissingField),
+                };
+
+                match local::Date::ymd(year, month, day) {
+                    Ok(date) => date,
+                    Err(_)    => return Err(ParseError::OutOfRange),
+                }
+            },
+        };
+
+        if let Some(weekday) = self.weekday {
+            if weekday_from_index(weekday) != date.weekday() {
+                return Err(ParseError::InconsistentFields);
+            }
+        }
+
+        Ok(date)
+    }
+}
+
+static LONG_MONTH_NAMES:  [&'static str; 12] = ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+static SHORT_MONTH_NAMES: [&'static str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+static LONG_DAY_NAMES:    [&'static str; 7]  = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+static SHORT_DAY_NAMES:   [&'static str; 7]  = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+fn month_from_index(index: usize) -> local::Month {
+    use local::Month::*;
+    [January, February, March, April, May, June, July, August, September, October, November, December][index]
+}
+
+fn weekday_from_index(index: usize) -> local::Weekday {
+    use local::Weekday::*;
+    [Monday, Tuesday, Wednesday, Thursday, Friday, Saturday, Sunday][index]
+}
+
+/// Skips any leading whitespace, then an optional `-` sign, then greedily
+/// reads up to `max_digits` decimal digits from the input starting at
+/// `cursor`, returning the parsed number and the cursor position just past
+/// it. The sign doesn't count against `max_digits`, so that e.g. a 4-digit
+/// year field can still read `-400` in full.
+fn parse_number<'a>(input: &'a str, cursor: usize, max_digits: usize) -> Result<(i64, usize), ParseError<'a>> {
+    let bytes = input.as_bytes();
+    let mut pos = cursor;
+
+    while pos < bytes.len() && (bytes[pos] == b' ' || bytes[pos] == b'\t' || bytes[pos] == b'\n' || bytes[pos] == b'\r') {
+        pos += 1;
+    }
+
+    let negative = pos < bytes.len() && bytes[pos] == b'-';
+    if negative {
+        pos += 1;
+    }
+
+    let start = pos;
+    while pos < bytes.len() && pos - start < max_digits && (bytes[pos] as char).is_digit(10) {
+        pos += 1;
+    }
+
+    if start == pos {
+        return Err(ParseError::InvalidDigit { pos: start });
+    }
+
+    let sign_start = if negative { start - 1 } else { start };
+    match input[sign_start..pos].parse() {
+        Ok(value) => Ok((value, pos)),
+        Err(_)     => Err(ParseError::InvalidDigit { pos: start }),
+    }
+}
+
+/// Matches the input at `cursor` case-insensitively against every name in
+/// both tables, preferring the longest match (so that, say, `"June"`
+/// matches the long name rather than being cut short at the `"Jun"` short
+/// name). Returns the index of the matching name within its table.
+fn match_name<'a>(input: &'a str, cursor: usize, long_names: &[&'static str], short_names: &[&'static str]) -> Result<(usize, usize), ParseError<'a>> {
+    let remaining = &input[cursor..];
+    let mut best: Option<(usize, usize)> = None;  // (byte length matched, index into its table)
+
+    for (index, name) in long_names.iter().chain(short_names.iter()).enumerate() {
+        // Indices past `long_names.len()` belong to `short_names`, so wrap
+        // them back round to the short table's own indexing.
+        let index = if index < long_names.len() { index } else { index - long_names.len() };
+
+        if starts_with_ignore_case(remaining, name) {
+            let is_better = match best {
+                Some((len, _)) => name.len() > len,
+                None            => true,
+            };
+
+            if is_better {
+                best = Some((name.len(), index));
+            }
+        }
+    }
+
+    match best {
+        Some((len, index)) => Ok((index, cursor + len)),
+        None                 => Err(ParseError::UnknownName { pos: cursor }),
+    }
+}
+
+fn starts_with_ignore_case(haystack: &str, needle: &str) -> bool {
+    let mut haystack_chars = haystack.chars();
+
+    for needle_char in needle.chars() {
+        match haystack_chars.next() {
+            Some(c) if c.to_ascii_lowercase() == needle_char.to_ascii_lowercase() => {},
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+fn month_number(month: local::Month) -> i64 {
+    use local::Month::*;
+    match month {
+        January   => 1,   February  => 2,
+        March     => 3,   April     => 4,
+        May       => 5,   June      => 6,
+        July      => 7,   August    => 8,
+        September => 9,   October   => 10,
+        November  => 11,  December  => 12,
+    }
 }
 
 fn long_month_name(month: local::Month) -> &'static str {
@@ -285,6 +819,7 @@ fn short_day_name(day: local::Weekday) -> &'static str {
 mod test {
     pub use super::{DateFormat, FormatError, Field, Arguments, NumArguments, TextArguments};
     pub use super::Field::*;
+    pub use super::Alignment;
 
     mod parse {
         use super::*;
@@ -319,7 +854,9 @@ mod test {
 
         test!(missing_field: "{}"                              => Err(FormatError::MissingField { open_pos: 0, close_pos: 1 }));
         test!(invalid_char: "{7}"                              => Err(FormatError::InvalidChar { c: '7', colon: false, pos: 1 }));
-        test!(invalid_char_after_colon: "{:7}"                 => Err(FormatError::InvalidChar { c: '7', colon: true, pos: 2 }));
+        test!(spec_with_no_field: "{:7}"                       => Err(FormatError::MissingField { open_pos: 1, close_pos: 3 }));
+        test!(invalid_format_spec: "{:@Y}"                     => Err(FormatError::InvalidFormatSpec { pos: 2 }));
+        test!(width_too_big_to_fit_a_usize: "{:99999999999999999999Y}" => Err(FormatError::InvalidFormatSpec { pos: 2 }));
         test!(open_curly_brace: "{"                            => Err(FormatError::OpenCurlyBrace { open_pos: 0 }));
         test!(mystery_close_brace: "}"                         => Err(FormatError::CloseCurlyBrace { close_pos: 0 }));
         test!(another_mystery_close_brace: "This is a test: }" => Err(FormatError::CloseCurlyBrace { close_pos: 16 }));
@@ -329,5 +866,134 @@ mod test {
 
         test!(escaping_middle: "The character {{ is my favourite!" => Ok(DateFormat { fields: vec![ Literal("The character "), Literal("{"), Literal(" is my favourite!") ] }));
         test!(escaping_middle_2: "It's way better than }}."        => Ok(DateFormat { fields: vec![ Literal("It's way better than "), Literal("}"), Literal(".") ] }));
+
+        fn year_with(args: Arguments) -> Field<'static> {
+            Year(NumArguments { args: args })
+        }
+
+        fn day_with(args: Arguments) -> Field<'static> {
+            Day(NumArguments { args: args })
+        }
+
+        test!(zero_padded_year: "{:04Y}" => Ok(DateFormat { fields: vec![
+            year_with(Arguments { alignment: Some(Alignment::Right), width: Some(4), pad_char: Some('0') })
+        ] }));
+
+        test!(fill_and_align: "{:*^6D}" => Ok(DateFormat { fields: vec![
+            day_with(Arguments { alignment: Some(Alignment::Middle), width: Some(6), pad_char: Some('*') })
+        ] }));
+
+        test!(alignment_only: "{:>Y}" => Ok(DateFormat { fields: vec![
+            year_with(Arguments { alignment: Some(Alignment::Right), width: None, pad_char: None })
+        ] }));
+    }
+
+    mod parse_strftime {
+        use super::*;
+
+        macro_rules! test {
+            ($name: ident: $input: expr => $result: expr) => {
+                #[test]
+                fn $name() {
+                    assert_eq!(DateFormat::parse_strftime($input), $result)
+                }
+            };
+        }
+
+        fn zero_padded(width: usize) -> Arguments {
+            Arguments { alignment: Some(Alignment::Right), width: Some(width), pad_char: Some('0') }
+        }
+
+        test!(year_month_day: "%Y-%m-%d" => Ok(DateFormat { fields: vec![
+            Year(NumArguments { args: Arguments::empty() }),
+            Literal("-"),
+            MonthNumber(NumArguments { args: zero_padded(2) }),
+            Literal("-"),
+            Day(NumArguments { args: zero_padded(2) }),
+        ] }));
+
+        test!(weekday_and_month_names: "%A, %d %B %Y" => Ok(DateFormat { fields: vec![
+            WeekdayName(true, TextArguments { args: Arguments::empty() }),
+            Literal(", "),
+            Day(NumArguments { args: zero_padded(2) }),
+            Literal(" "),
+            MonthName(true, TextArguments { args: Arguments::empty() }),
+            Literal(" "),
+            Year(NumArguments { args: Arguments::empty() }),
+        ] }));
+
+        test!(day_of_year: "%Y-%j" => Ok(DateFormat { fields: vec![
+            Year(NumArguments { args: Arguments::empty() }),
+            Literal("-"),
+            DayOfYear(NumArguments { args: zero_padded(3) }),
+        ] }));
+
+        test!(literal_percent: "100%%" => Ok(DateFormat { fields: vec![ Literal("100"), Literal("%") ] }));
+
+        test!(unsupported_hour: "%H:%M" => Err(FormatError::UnsupportedField { specifier: 'H', pos: 1 }));
+    }
+
+    mod parse_date {
+        use super::super::local;
+        use super::super::ParseError;
+        use super::*;
+
+        macro_rules! test {
+            ($name: ident: $format: expr, $input: expr => $result: expr) => {
+                #[test]
+                fn $name() {
+                    let format = DateFormat::parse($format).unwrap();
+                    assert_eq!(format.parse_date($input), $result)
+                }
+            };
+        }
+
+        test!(basic: "{:Y}-{:M}-{:D}", "2015-September-13"
+            => Ok(local::Date::ymd(2015, local::Month::September, 13).unwrap()));
+
+        test!(short_month_name: "{:D} {:M} {:Y}", "13 Sep 2015"
+            => Ok(local::Date::ymd(2015, local::Month::September, 13).unwrap()));
+
+        test!(missing_month: "{:D}-{:Y}", "13-2015" => Err(ParseError::MissingField));
+
+        test!(literal_mismatch: "{:Y}-{:M}-{:D}", "2015/September/13"
+            => Err(ParseError::LiteralMismatch { expected: "-", pos: 4 }));
+
+        test!(inconsistent_year: "{:Y} {:y}", "2015 99" => Err(ParseError::InconsistentFields));
+    }
+
+    mod rfc {
+        use super::super::local;
+
+        #[test]
+        fn to_rfc3339() {
+            let date = local::Date::ymd(2015, local::Month::September, 13).unwrap();
+            assert_eq!(date.to_rfc3339(), "2015-09-13");
+        }
+
+        #[test]
+        fn round_trips_rfc3339() {
+            let date = local::Date::ymd(2015, local::Month::September, 13).unwrap();
+            assert_eq!(local::Date::parse_from_rfc3339(&date.to_rfc3339()), Ok(date));
+        }
+
+        #[test]
+        fn parses_rfc2822() {
+            let date = local::Date::ymd(2015, local::Month::February, 18).unwrap();
+            assert_eq!(local::Date::parse_from_rfc2822("Wed, 18 Feb 2015"), Ok(date));
+        }
+
+        #[test]
+        fn to_rfc3339_zero_pads_a_year_before_1000() {
+            let date = local::Date::ymd(42, local::Month::January, 1).unwrap();
+            assert_eq!(date.to_rfc3339(), "0042-01-01");
+        }
+
+        #[test]
+        fn round_trips_rfc3339_with_a_negative_year() {
+            let date = local::Date::ymd(-5, local::Month::January, 1).unwrap();
+            assert_eq!(date.to_rfc3339(), "-005-01-01");
+            assert_eq!(local::Date::parse_from_rfc3339(&date.to_rfc3339()), Ok(date));
+        }
     }
 }
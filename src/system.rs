@@ -51,9 +51,126 @@ pub unsafe fn sys_time() -> (i64, i16) {
    (ts.tv_sec, (ts.tv_nsec / 1000) as i16)
 }
 
+/// The number of seconds between the Windows epoch (1601-01-01) and the
+/// Unix epoch (1970-01-01), used to convert `FILETIME` values below.
+#[cfg(windows)]
+const WINDOWS_EPOCH_DIFFERENCE: i64 = 11_644_473_600;
+
+#[cfg(windows)]
+#[repr(C)]
+struct FileTime {
+    dw_low_date_time:  u32,
+    dw_high_date_time: u32,
+}
+
+#[cfg(windows)]
+extern "system" {
+    fn GetSystemTimeAsFileTime(file_time: *mut FileTime);
+}
+
+/// Returns the system’s current time, as a tuple of seconds elapsed since
+/// the Unix epoch, and the millisecond of the second.
+///
+/// This calls `GetSystemTimeAsFileTime` rather than the more precise
+/// `GetSystemTimePreciseAsFileTime`, which is only present from Windows 8
+/// onwards and would need to be loaded dynamically to support older
+/// systems — not worth the complexity for millisecond resolution.
+#[cfg(windows)]
+pub unsafe fn sys_time() -> (i64, i16) {
+    let mut file_time = FileTime { dw_low_date_time: 0, dw_high_date_time: 0 };
+    GetSystemTimeAsFileTime(&mut file_time);
+
+    let ticks = ((file_time.dw_high_date_time as u64) << 32) | (file_time.dw_low_date_time as u64);
+    let unix_seconds = (ticks / 10_000_000) as i64 - WINDOWS_EPOCH_DIFFERENCE;
+    let milliseconds = ((ticks / 10_000) % 1000) as i16;
+
+    (unix_seconds, milliseconds)
+}
+
+#[cfg(windows)]
+#[repr(C)]
+struct SystemTime {
+    w_year:         u16,
+    w_month:        u16,
+    w_day_of_week:  u16,
+    w_day:          u16,
+    w_hour:         u16,
+    w_minute:       u16,
+    w_second:       u16,
+    w_milliseconds: u16,
+}
+
+#[cfg(windows)]
+#[repr(C)]
+struct TimeZoneInformation {
+    bias:           i32,
+    standard_name:  [u16; 32],
+    standard_date:  SystemTime,
+    standard_bias:  i32,
+    daylight_name:  [u16; 32],
+    daylight_date:  SystemTime,
+    daylight_bias:  i32,
+}
+
+#[cfg(windows)]
+extern "system" {
+    fn GetTimeZoneInformation(tz_info: *mut TimeZoneInformation) -> u32;
+}
+
+/// Attempts to determine the system’s current time zone by asking
+/// `GetTimeZoneInformation` for the standard-time name of the zone Windows
+/// has configured, then mapping that name to its IANA equivalent.
+///
+/// Only a small sample of Windows zone names are mapped here; a complete
+/// mapping would mean vendoring the much larger table the Unicode CLDR
+/// project publishes as `windowsZones.xml`. Anything not in the sample
+/// returns `None`, the same as when no timezone can be found at all.
+#[cfg(windows)]
+pub fn sys_timezone() -> Option<String> {
+    const TIME_ZONE_ID_INVALID: u32 = 0xFFFFFFFF;
+
+    let mut info = TimeZoneInformation {
+        bias:          0,
+        standard_name: [0; 32],
+        standard_date: SystemTime { w_year: 0, w_month: 0, w_day_of_week: 0, w_day: 0, w_hour: 0, w_minute: 0, w_second: 0, w_milliseconds: 0 },
+        standard_bias: 0,
+        daylight_name: [0; 32],
+        daylight_date: SystemTime { w_year: 0, w_month: 0, w_day_of_week: 0, w_day: 0, w_hour: 0, w_minute: 0, w_second: 0, w_milliseconds: 0 },
+        daylight_bias: 0,
+    };
+
+    if unsafe { GetTimeZoneInformation(&mut info) } == TIME_ZONE_ID_INVALID {
+        return None;
+    }
+
+    let name = utf16_buf_to_string(&info.standard_name);
+    windows_zone_to_iana(&name)
+}
+
+#[cfg(windows)]
+fn utf16_buf_to_string(buf: &[u16]) -> String {
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    String::from_utf16_lossy(&buf[..len])
+}
+
+#[cfg(windows)]
+fn windows_zone_to_iana(name: &str) -> Option<String> {
+    match name {
+        "GMT Standard Time"      => Some("Europe/London".to_string()),
+        "Eastern Standard Time"  => Some("America/New_York".to_string()),
+        "Central Standard Time"  => Some("America/Chicago".to_string()),
+        "Mountain Standard Time" => Some("America/Denver".to_string()),
+        "Pacific Standard Time"  => Some("America/Los_Angeles".to_string()),
+        "China Standard Time"    => Some("Asia/Shanghai".to_string()),
+        "Tokyo Standard Time"    => Some("Asia/Tokyo".to_string()),
+        _                        => None,
+    }
+}
+
 /// Attempts to determine the system’s current time zone. There’s no
 /// guaranteed way to do this, so this function returns `None` if no
 /// timezone could be found.
+#[cfg(not(windows))]
 pub fn sys_timezone() -> Option<String> {
     use std::fs::read_link;
 
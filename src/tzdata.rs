@@ -0,0 +1,859 @@
+//! Parsing for the human-readable IANA tzdata *source* format — the
+//! `Rule`/`Zone`/`Link` syntax used by files such as `europe` or
+//! `northamerica` in the tz database distribution — as opposed to `tzif`,
+//! which reads the binary output that `zic` compiles those files into.
+//!
+//! Parsing these directly lets this crate build its own offset tables
+//! without needing a compiled zoneinfo tree to be present on the system.
+
+use std::collections::HashMap;
+
+/// A month, named the way tzdata source files write it (`Jan`, `Apr`, ...).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Month { January, February, March, April, May, June, July, August, September, October, November, December }
+
+impl Month {
+    fn from_abbreviation(s: &str) -> Option<Month> {
+        use self::Month::*;
+        match s {
+            "Jan" => Some(January),   "Feb" => Some(February),
+            "Mar" => Some(March),     "Apr" => Some(April),
+            "May" => Some(May),       "Jun" => Some(June),
+            "Jul" => Some(July),      "Aug" => Some(August),
+            "Sep" => Some(September), "Oct" => Some(October),
+            "Nov" => Some(November),  "Dec" => Some(December),
+            _     => None,
+        }
+    }
+}
+
+/// A weekday, named the way tzdata source files write it (`Sun`, `Mon`, ...).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Weekday { Sunday, Monday, Tuesday, Wednesday, Thursday, Friday, Saturday }
+
+impl Weekday {
+    fn from_abbreviation(s: &str) -> Option<Weekday> {
+        use self::Weekday::*;
+        match s {
+            "Sun" => Some(Sunday),    "Mon" => Some(Monday),
+            "Tue" => Some(Tuesday),   "Wed" => Some(Wednesday),
+            "Thu" => Some(Thursday),  "Fri" => Some(Friday),
+            "Sat" => Some(Saturday),
+            _     => None,
+        }
+    }
+}
+
+/// The `ON` column of a `Rule` line, or the day portion of a `Zone`/zone
+/// continuation line's `UNTIL` column: either a bare day-of-month, the last
+/// occurrence of a weekday in the month, or the first/last occurrence of a
+/// weekday on or after/before a given day-of-month.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum OnDay {
+    Numbered(u8),
+    Last(Weekday),
+    AtLeast(Weekday, u8),
+    AtMost(Weekday, u8),
+}
+
+/// Which clock the `AT` column's time is measured against, as indicated by
+/// its trailing `w`/`s`/`u`/`g`/`z` suffix (`u`, `g`, and `z` are all
+/// synonyms for UTC). Defaults to `Wall` when no suffix is present.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Clock { Wall, Standard, Utc }
+
+/// A time of day, read out of an `AT` or `UNTIL` column.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct At {
+    pub seconds: i64,
+    pub clock:   Clock,
+}
+
+/// The `FROM`/`TO` columns of a `Rule` line, which can be a literal year or
+/// one of the `min`/`max`/`only` keywords.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Year { Minimum, Maximum, Only, Numbered(i32) }
+
+/// A single `Rule` line: one entry in a named set of recurring DST
+/// transitions.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Rule {
+    pub name:    String,
+    pub from:    Year,
+    pub to:      Year,
+    pub month:   Month,
+    pub on:      OnDay,
+    pub at:      At,
+    pub save:    i64,
+    pub letters: String,
+}
+
+/// The `RULES`/`SAVE` column of a `Zone` line: either no DST rules at all,
+/// a reference to a named `Rule` set, or a fixed UTC offset applied with no
+/// rules backing it.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum RulesColumn {
+    None,
+    Named(String),
+    Save(i64),
+}
+
+/// The optional `UNTIL` column of a `Zone`/zone continuation line, which
+/// ends that line's applicability. Columns omitted from the source default
+/// to the start of the year (month January, day 1, midnight wall clock).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Until {
+    pub year:  i32,
+    pub month: Month,
+    pub on:    OnDay,
+    pub at:    At,
+}
+
+/// One line of a `Zone` block: either the first line (which carries the
+/// zone's name) or a continuation line (which doesn't).
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ZoneLine {
+    pub gmtoff: i64,
+    pub rules:  RulesColumn,
+    pub format: String,
+    pub until:  Option<Until>,
+}
+
+/// A `Link` line, aliasing one zone name to another.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Link {
+    pub target: String,
+    pub alias:  String,
+}
+
+/// One non-blank, non-comment line read out of a tzdata source file.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Entry {
+    Rule(Rule),
+    Zone(String, ZoneLine),
+    ZoneContinuation(ZoneLine),
+    Link(Link),
+}
+
+/// Something that went wrong parsing a line of tzdata source. Every
+/// variant carries the 1-indexed source line it occurred on.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum TzdataError {
+    TooFewFields     { line: usize, kind: &'static str },
+    UnknownLineKind  { line: usize, text: String },
+    UnknownMonth     { line: usize, text: String },
+    UnknownWeekday   { line: usize, text: String },
+    InvalidOnSpec    { line: usize, text: String },
+    InvalidTime      { line: usize, text: String },
+    InvalidYear      { line: usize, text: String },
+    InvalidOffset    { line: usize, text: String },
+}
+
+/// Parses every `Rule`, `Zone`, zone continuation, and `Link` line in a
+/// tzdata source file, in order. Blank lines and `#`-comments are skipped.
+///
+/// Zone continuation lines are returned as `Entry::ZoneContinuation` rather
+/// than being folded into the preceding `Entry::Zone`; a continuation line
+/// always belongs to the most recent `Entry::Zone`/`Entry::ZoneContinuation`
+/// above it. Most callers want the zone-name-keyed `Database` that `parse`
+/// builds out of these entries, rather than this flat line-by-line form.
+pub fn parse_source(input: &str) -> Result<Vec<Entry>, TzdataError> {
+    let mut entries = Vec::new();
+
+    for (index, raw_line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let without_comment = strip_comment(raw_line);
+
+        if without_comment.trim().is_empty() {
+            continue;
+        }
+
+        let is_continuation = without_comment.starts_with(' ') || without_comment.starts_with('\t');
+        let fields: Vec<&str> = without_comment.split_whitespace().collect();
+
+        if is_continuation {
+            let zone_line = try!(parse_zone_fields(&fields, line_number, 0));
+            entries.push(Entry::ZoneContinuation(zone_line));
+            continue;
+        }
+
+        match fields[0] {
+            "Rule" => {
+                let rule = try!(parse_rule_fields(&fields, line_number));
+                entries.push(Entry::Rule(rule));
+            },
+
+            "Zone" => {
+                if fields.len() < 2 {
+                    return Err(TzdataError::TooFewFields { line: line_number, kind: "Zone" });
+                }
+
+                let name      = fields[1].to_string();
+                let zone_line = try!(parse_zone_fields(&fields, line_number, 2));
+                entries.push(Entry::Zone(name, zone_line));
+            },
+
+            "Link" => {
+                if fields.len() < 3 {
+                    return Err(TzdataError::TooFewFields { line: line_number, kind: "Link" });
+                }
+
+                entries.push(Entry::Link(Link { target: fields[1].to_string(), alias: fields[2].to_string() }));
+            },
+
+            _ => return Err(TzdataError::UnknownLineKind { line: line_number, text: raw_line.to_string() }),
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Every `Rule`, `Zone`, and `Link` in a tzdata source file, keyed the way
+/// the format's cross-references need them to be: rules by the name their
+/// `Zone` lines refer to in the `RULES` column, and each zone's lines (its
+/// `Zone` line plus any continuation lines) by the zone's name. This is
+/// what `Date`/`YearMonthDay` should consult to compute offsets, rather
+/// than walking the flat `Entry` list `parse_source` returns.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Database {
+    pub rules: HashMap<String, Vec<Rule>>,
+    pub zones: HashMap<String, Vec<ZoneLine>>,
+    pub links: HashMap<String, String>,
+}
+
+impl Database {
+
+    /// Groups a flat list of entries, such as the one `parse_source`
+    /// returns, into a `Database`. A `ZoneContinuation` is folded into
+    /// whichever `Zone` most recently appeared before it.
+    pub fn from_entries(entries: Vec<Entry>) -> Database {
+        let mut rules = HashMap::new();
+        let mut zones: HashMap<String, Vec<ZoneLine>> = HashMap::new();
+        let mut links = HashMap::new();
+        let mut current_zone: Option<String> = None;
+
+        for entry in entries {
+            match entry {
+                Entry::Rule(rule) => {
+                    rules.entry(rule.name.clone()).or_insert_with(Vec::new).push(rule);
+                },
+
+                Entry::Zone(name, zone_line) => {
+                    zones.entry(name.clone()).or_insert_with(Vec::new).push(zone_line);
+                    current_zone = Some(name);
+                },
+
+                Entry::ZoneContinuation(zone_line) => {
+                    if let Some(ref name) = current_zone {
+                        zones.get_mut(name).unwrap().push(zone_line);
+                    }
+                },
+
+                Entry::Link(link) => {
+                    links.insert(link.alias, link.target);
+                },
+            }
+        }
+
+        Database { rules: rules, zones: zones, links: links }
+    }
+
+    /// Follows `Link` aliases until it reaches a name with `Zone` lines of
+    /// its own (or gives up after enough hops to rule out a cycle).
+    fn resolve_link<'b>(&'b self, zone_name: &'b str) -> &'b str {
+        let mut name = zone_name;
+
+        for _ in 0 .. self.links.len() + 1 {
+            match self.links.get(name) {
+                Some(target) => name = target,
+                None          => return name,
+            }
+        }
+
+        name
+    }
+
+    /// Returns the UTC offset, in seconds, in effect for the named zone at
+    /// the given Unix timestamp.
+    ///
+    /// This resolves `Link` aliases, picks whichever of the zone's
+    /// `ZoneLine`s has the timestamp before its `UNTIL` boundary (or the
+    /// last line, if none has one), and adds in a daylight-saving `save`
+    /// by evaluating that line's `Rule` set against the timestamp's
+    /// calendar year. See `active_save` for the approximation this makes
+    /// when resolving which rule was in effect.
+    pub fn offset_at(&self, zone_name: &str, timestamp: i64) -> Option<i64> {
+        let name  = self.resolve_link(zone_name);
+        let lines = match self.zones.get(name) {
+            Some(lines) => lines,
+            None         => return None,
+        };
+
+        for line in lines {
+            let applies = match line.until {
+                None             => true,
+                Some(ref until)  => timestamp < self.until_timestamp(line, until),
+            };
+
+            if applies {
+                return Some(line.gmtoff + self.save_for(line, timestamp));
+            }
+        }
+
+        // Every line had an `UNTIL` that's already passed, and none is a
+        // reasonable match, which shouldn't happen for a well-formed tz
+        // database; fall back to the last line's bare `GMTOFF` rather than
+        // returning nothing.
+        lines.last().map(|line| line.gmtoff)
+    }
+
+    /// Returns the daylight-saving `save`, in seconds, that `line`'s
+    /// `RULES` column contributes at the given timestamp: zero for `-`, the
+    /// fixed amount for a literal `SAVE` column, or whatever `active_save`
+    /// finds for a named `Rule` set.
+    fn save_for(&self, line: &ZoneLine, timestamp: i64) -> i64 {
+        match line.rules {
+            RulesColumn::None             => 0,
+            RulesColumn::Save(seconds)    => seconds,
+            RulesColumn::Named(ref name)  => self.active_save(name, line.gmtoff, timestamp),
+        }
+    }
+
+    /// Returns the `save` in effect for the named `Rule` set at the given
+    /// timestamp, by finding the most recent matching rule's transition
+    /// (in the timestamp's calendar year or the year before, since a rule
+    /// dated in December can still be active in January) that falls at or
+    /// before it.
+    ///
+    /// Each candidate rule's own transition instant is computed with
+    /// `save` assumed to be zero; this is the same "what was the save
+    /// immediately before this transition" chicken-and-egg problem `zic`
+    /// itself has to resolve, and a rule's `AT` column is overwhelmingly
+    /// given against the standard (non-DST) wall clock in practice, so the
+    /// approximation only matters for the rare rule written in DST wall
+    /// time.
+    fn active_save(&self, rule_name: &str, gmtoff: i64, timestamp: i64) -> i64 {
+        let rules = match self.rules.get(rule_name) {
+            Some(rules) => rules,
+            None         => return 0,
+        };
+
+        let (year, _, _) = civil_from_days(floor_div(timestamp, 86400));
+
+        let mut latest: Option<(i64, i64)> = None;
+
+        for &candidate_year in &[year - 1, year] {
+            for rule in rules {
+                if !year_matches(rule, candidate_year) {
+                    continue;
+                }
+
+                let transition = timestamp_for(candidate_year, rule.month, rule.on, rule.at, gmtoff, 0);
+
+                if transition <= timestamp && latest.map_or(true, |(t, _)| transition > t) {
+                    latest = Some((transition, rule.save));
+                }
+            }
+        }
+
+        latest.map_or(0, |(_, save)| save)
+    }
+
+    /// Resolves a `ZoneLine`'s `UNTIL` column into the Unix timestamp at
+    /// which it stops applying, first approximating the boundary with
+    /// `save` assumed to be zero to find which `save` was actually active
+    /// at that instant, then recomputing with it. (`active_save` makes only
+    /// the first of these passes for a rule's own transition.)
+    fn until_timestamp(&self, line: &ZoneLine, until: &Until) -> i64 {
+        let approx = timestamp_for(until.year as i64, until.month, until.on, until.at, line.gmtoff, 0);
+
+        let save = match line.rules {
+            RulesColumn::None             => 0,
+            RulesColumn::Save(seconds)    => seconds,
+            RulesColumn::Named(ref name)  => self.active_save(name, line.gmtoff, approx),
+        };
+
+        timestamp_for(until.year as i64, until.month, until.on, until.at, line.gmtoff, save)
+    }
+}
+
+/// Whether `year` falls within a `Rule`'s `FROM`/`TO` range.
+fn year_matches(rule: &Rule, year: i64) -> bool {
+    let from = match rule.from {
+        Year::Numbered(y) => y as i64,
+        Year::Minimum      => ::std::i64::MIN,
+        Year::Maximum      => ::std::i64::MAX,
+        Year::Only         => year,
+    };
+
+    let to = match rule.to {
+        Year::Numbered(y) => y as i64,
+        Year::Minimum      => ::std::i64::MIN,
+        Year::Maximum      => ::std::i64::MAX,
+        Year::Only         => from,
+    };
+
+    year >= from && year <= to
+}
+
+/// Converts a tzdata `(year, month, on-spec, at-spec)` into the Unix
+/// timestamp it names, given the zone's standard offset and the
+/// daylight-saving `save` in effect, so that `at.clock`'s `Wall` and
+/// `Standard` variants can be turned into UTC.
+fn timestamp_for(year: i64, month: Month, on: OnDay, at: At, gmtoff: i64, save: i64) -> i64 {
+    let day      = resolve_on_day(year, month, on);
+    let midnight = days_from_civil(year, month_number(month), day) * 86400;
+    let local    = midnight + at.seconds;
+
+    match at.clock {
+        Clock::Utc      => local,
+        Clock::Standard => local - gmtoff,
+        Clock::Wall     => local - gmtoff - save,
+    }
+}
+
+/// Resolves an `OnDay` spec to a day-of-month, for the given year and
+/// month.
+fn resolve_on_day(year: i64, month: Month, on: OnDay) -> i64 {
+    match on {
+        OnDay::Numbered(day) => day as i64,
+
+        OnDay::Last(weekday) => {
+            let mut day = days_in_month(year, month);
+            while weekday_on(year, month, day) != weekday { day -= 1; }
+            day
+        },
+
+        OnDay::AtLeast(weekday, start) => {
+            let mut day = start as i64;
+            while weekday_on(year, month, day) != weekday { day += 1; }
+            day
+        },
+
+        OnDay::AtMost(weekday, start) => {
+            let mut day = start as i64;
+            while weekday_on(year, month, day) != weekday { day -= 1; }
+            day
+        },
+    }
+}
+
+/// The weekday that a given year/month/day falls on.
+fn weekday_on(year: i64, month: Month, day: i64) -> Weekday {
+    let days = days_from_civil(year, month_number(month), day);
+
+    match weekday_from_days(days) {
+        0 => Weekday::Sunday,    1 => Weekday::Monday,
+        2 => Weekday::Tuesday,   3 => Weekday::Wednesday,
+        4 => Weekday::Thursday,  5 => Weekday::Friday,
+        _ => Weekday::Saturday,
+    }
+}
+
+fn month_number(month: Month) -> i64 {
+    use self::Month::*;
+    match month {
+        January => 1,   February => 2,  March     => 3,  April    => 4,
+        May     => 5,   June     => 6,  July      => 7,  August   => 8,
+        September => 9, October  => 10, November  => 11, December => 12,
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: Month) -> i64 {
+    use self::Month::*;
+    match month {
+        January | March | May | July | August | October | December => 31,
+        April | June | September | November                         => 30,
+        February => if is_leap_year(year) { 29 } else { 28 },
+    }
+}
+
+/// The weekday of the given day count since the Unix epoch, as `0` for
+/// Sunday through `6` for Saturday. Ported from Howard Hinnant's
+/// `weekday_from_days`, using the fact that day `0` (1970-01-01) was a
+/// Thursday.
+fn weekday_from_days(z: i64) -> i64 {
+    if z >= -4 { (z + 4) % 7 } else { (z + 5) % 7 + 6 }
+}
+
+/// The day count since the Unix epoch for the given proleptic Gregorian
+/// civil date (`month` is `1..=12`). Ported from Howard Hinnant's
+/// `days_from_civil`, which this crate uses instead of its own `local`
+/// module so that `tzdata`'s date arithmetic (resolving `lastSun`,
+/// `Sun>=8`, and the like) doesn't need to round-trip through it.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// The proleptic Gregorian civil date, as `(year, month, day)`, for the
+/// given day count since the Unix epoch. The inverse of `days_from_civil`.
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z   = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y   = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp  = (5 * doy + 2) / 153;
+    let d   = doy - (153 * mp + 2) / 5 + 1;
+    let m   = if mp < 10 { mp + 3 } else { mp - 9 };
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+/// Floor division: like `/`, but rounds towards negative infinity instead
+/// of towards zero, which matters for turning a negative timestamp into a
+/// day count.
+fn floor_div(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    let r = a % b;
+
+    if r != 0 && (r < 0) != (b < 0) { q - 1 } else { q }
+}
+
+/// Parses a tzdata source file straight into a zone-name-keyed `Database`.
+pub fn parse(input: &str) -> Result<Database, TzdataError> {
+    let entries = try!(parse_source(input));
+    Ok(Database::from_entries(entries))
+}
+
+/// Cuts a line off at its first `#`, the same way zic treats the rest of
+/// the line as a comment from there on.
+fn strip_comment(line: &str) -> &str {
+    match line.find('#') {
+        Some(pos) => &line[..pos],
+        None        => line,
+    }
+}
+
+fn parse_rule_fields(fields: &[&str], line_number: usize) -> Result<Rule, TzdataError> {
+    if fields.len() < 10 {
+        return Err(TzdataError::TooFewFields { line: line_number, kind: "Rule" });
+    }
+
+    let name  = fields[1].to_string();
+    let from  = try!(parse_year(fields[2], line_number));
+    let to    = try!(parse_year(fields[3], line_number));
+    // fields[4] is the historical "type" column; every tzdata file in
+    // practice leaves it as "-", and nothing here needs it.
+    let month = try!(parse_month(fields[5], line_number));
+    let on    = try!(parse_on_day(fields[6], line_number));
+    let at    = try!(parse_at(fields[7], line_number));
+    let save  = try!(parse_offset_seconds(fields[8], line_number));
+
+    Ok(Rule { name: name, from: from, to: to, month: month, on: on, at: at, save: save, letters: fields[9].to_string() })
+}
+
+/// Parses the `GMTOFF RULES FORMAT [UNTIL...]` columns shared by both a
+/// `Zone` line and its continuation lines; `start` is the index of the
+/// `GMTOFF` field, which is 2 for a `Zone` line (after `Zone` and the zone
+/// name) or 0 for a continuation line.
+fn parse_zone_fields(fields: &[&str], line_number: usize, start: usize) -> Result<ZoneLine, TzdataError> {
+    if fields.len() < start + 3 {
+        return Err(TzdataError::TooFewFields { line: line_number, kind: "Zone" });
+    }
+
+    let gmtoff = try!(parse_offset_seconds(fields[start], line_number));
+    let rules  = try!(parse_rules_column(fields[start + 1], line_number));
+    let format = fields[start + 2].to_string();
+
+    let until = if fields.len() > start + 3 {
+        Some(try!(parse_until(&fields[start + 3 ..], line_number)))
+    }
+    else {
+        None
+    };
+
+    Ok(ZoneLine { gmtoff: gmtoff, rules: rules, format: format, until: until })
+}
+
+fn parse_rules_column(field: &str, line_number: usize) -> Result<RulesColumn, TzdataError> {
+    if field == "-" {
+        return Ok(RulesColumn::None);
+    }
+
+    let is_fixed_save = field.chars().next().map_or(false, |c| c.is_digit(10) || c == '-');
+    if is_fixed_save {
+        let seconds = try!(parse_offset_seconds(field, line_number));
+        Ok(RulesColumn::Save(seconds))
+    }
+    else {
+        Ok(RulesColumn::Named(field.to_string()))
+    }
+}
+
+fn parse_until(fields: &[&str], line_number: usize) -> Result<Until, TzdataError> {
+    let year = try!(parse_year_number(fields[0], line_number));
+
+    let month = if fields.len() > 1 { try!(parse_month(fields[1], line_number)) } else { Month::January };
+    let on    = if fields.len() > 2 { try!(parse_on_day(fields[2], line_number)) } else { OnDay::Numbered(1) };
+    let at    = if fields.len() > 3 { try!(parse_at(fields[3], line_number)) } else { At { seconds: 0, clock: Clock::Wall } };
+
+    Ok(Until { year: year, month: month, on: on, at: at })
+}
+
+fn parse_year_number(s: &str, line_number: usize) -> Result<i32, TzdataError> {
+    s.parse().map_err(|_| TzdataError::InvalidYear { line: line_number, text: s.to_string() })
+}
+
+fn parse_year(s: &str, line_number: usize) -> Result<Year, TzdataError> {
+    match s {
+        "min" | "minimum" => Ok(Year::Minimum),
+        "max" | "maximum" => Ok(Year::Maximum),
+        "only"            => Ok(Year::Only),
+        _                 => s.parse().map(Year::Numbered)
+                                .map_err(|_| TzdataError::InvalidYear { line: line_number, text: s.to_string() }),
+    }
+}
+
+fn parse_month(s: &str, line_number: usize) -> Result<Month, TzdataError> {
+    Month::from_abbreviation(s).ok_or_else(|| TzdataError::UnknownMonth { line: line_number, text: s.to_string() })
+}
+
+fn parse_weekday(s: &str, line_number: usize) -> Result<Weekday, TzdataError> {
+    Weekday::from_abbreviation(s).ok_or_else(|| TzdataError::UnknownWeekday { line: line_number, text: s.to_string() })
+}
+
+fn parse_day_number(s: &str, line_number: usize) -> Result<u8, TzdataError> {
+    s.parse().map_err(|_| TzdataError::InvalidOnSpec { line: line_number, text: s.to_string() })
+}
+
+fn parse_on_day(s: &str, line_number: usize) -> Result<OnDay, TzdataError> {
+    if s.starts_with("last") {
+        let weekday = try!(parse_weekday(&s[4..], line_number));
+        return Ok(OnDay::Last(weekday));
+    }
+
+    if let Some(pos) = s.find(">=") {
+        let weekday = try!(parse_weekday(&s[.. pos], line_number));
+        let day     = try!(parse_day_number(&s[pos + 2 ..], line_number));
+        return Ok(OnDay::AtLeast(weekday, day));
+    }
+
+    if let Some(pos) = s.find("<=") {
+        let weekday = try!(parse_weekday(&s[.. pos], line_number));
+        let day     = try!(parse_day_number(&s[pos + 2 ..], line_number));
+        return Ok(OnDay::AtMost(weekday, day));
+    }
+
+    let day = try!(parse_day_number(s, line_number));
+    Ok(OnDay::Numbered(day))
+}
+
+/// Parses the time-of-day part of an `AT` or `UNTIL` column: `H`, `H:MM`,
+/// or `H:MM:SS`, with an optional trailing `w`/`s`/`u`/`g`/`z` clock
+/// suffix. A bare `-` means midnight on the wall clock.
+fn parse_at(s: &str, line_number: usize) -> Result<At, TzdataError> {
+    if s == "-" {
+        return Ok(At { seconds: 0, clock: Clock::Wall });
+    }
+
+    let (time_part, clock) = match s.chars().last() {
+        Some('w')                       => (&s[.. s.len() - 1], Clock::Wall),
+        Some('s')                       => (&s[.. s.len() - 1], Clock::Standard),
+        Some('u') | Some('g') | Some('z') => (&s[.. s.len() - 1], Clock::Utc),
+        _                                => (s, Clock::Wall),
+    };
+
+    let seconds = try!(parse_time_seconds(time_part, line_number));
+    Ok(At { seconds: seconds, clock: clock })
+}
+
+/// Parses `[-]H[:MM[:SS]]` into a signed count of seconds, used by both the
+/// `GMTOFF`/`SAVE` columns and (via `parse_at`) the `AT`/`UNTIL` columns.
+fn parse_hms_seconds(s: &str) -> Option<i64> {
+    let negative = s.starts_with('-');
+    let unsigned = if negative { &s[1..] } else { s };
+
+    if unsigned.is_empty() {
+        return None;
+    }
+
+    let mut parts = unsigned.split(':');
+
+    let hours: i64   = match parts.next().and_then(|p| p.parse().ok()) { Some(v) => v, None => return None };
+    let minutes: i64 = match parts.next() { Some(p) => match p.parse().ok() { Some(v) => v, None => return None }, None => 0 };
+    let seconds: i64 = match parts.next() { Some(p) => match p.parse().ok() { Some(v) => v, None => return None }, None => 0 };
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let total = hours * 3600 + minutes * 60 + seconds;
+    Some(if negative { -total } else { total })
+}
+
+fn parse_offset_seconds(s: &str, line_number: usize) -> Result<i64, TzdataError> {
+    parse_hms_seconds(s).ok_or_else(|| TzdataError::InvalidOffset { line: line_number, text: s.to_string() })
+}
+
+fn parse_time_seconds(s: &str, line_number: usize) -> Result<i64, TzdataError> {
+    parse_hms_seconds(s).ok_or_else(|| TzdataError::InvalidTime { line: line_number, text: s.to_string() })
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_a_rule_line() {
+        let entries = parse_source("Rule    US    1967    1973    -    Apr    lastSun    2:00    1:00    D").unwrap();
+        assert_eq!(entries, vec![
+            Entry::Rule(Rule {
+                name:    "US".to_string(),
+                from:    Year::Numbered(1967),
+                to:      Year::Numbered(1973),
+                month:   Month::April,
+                on:      OnDay::Last(Weekday::Sunday),
+                at:      At { seconds: 7200, clock: Clock::Wall },
+                save:    3600,
+                letters: "D".to_string(),
+            }),
+        ]);
+    }
+
+    #[test]
+    fn parses_a_zone_and_its_continuation() {
+        let entries = parse_source(
+            "Zone America/New_York -4:56:02 -     LMT     1883 Nov 18 12:03:58
+                                   -5:00   US      E%sT    1920"
+        ).unwrap();
+
+        assert_eq!(entries.len(), 2);
+
+        match entries[0] {
+            Entry::Zone(ref name, ref line) => {
+                assert_eq!(name, "America/New_York");
+                assert_eq!(line.gmtoff, -(4 * 3600 + 56 * 60 + 2));
+                assert_eq!(line.rules, RulesColumn::None);
+                assert_eq!(line.format, "LMT");
+                assert_eq!(line.until.unwrap().year, 1883);
+            },
+            ref other => panic!("wrong entry: {:?}", other),
+        }
+
+        match entries[1] {
+            Entry::ZoneContinuation(ref line) => {
+                assert_eq!(line.gmtoff, -5 * 3600);
+                assert_eq!(line.rules, RulesColumn::Named("US".to_string()));
+                assert_eq!(line.format, "E%sT");
+                assert_eq!(line.until.unwrap().year, 1920);
+            },
+            ref other => panic!("wrong entry: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_a_link_line() {
+        let entries = parse_source("Link  America/New_York  US/Eastern").unwrap();
+        assert_eq!(entries, vec![
+            Entry::Link(Link { target: "America/New_York".to_string(), alias: "US/Eastern".to_string() }),
+        ]);
+    }
+
+    #[test]
+    fn ignores_blank_and_comment_lines() {
+        let entries = parse_source("# a comment\n\n   \nLink A B").unwrap();
+        assert_eq!(entries, vec![
+            Entry::Link(Link { target: "A".to_string(), alias: "B".to_string() }),
+        ]);
+    }
+
+    #[test]
+    fn parses_weekday_at_least_and_at_most() {
+        assert_eq!(parse_on_day("Sun>=8", 1),  Ok(OnDay::AtLeast(Weekday::Sunday, 8)));
+        assert_eq!(parse_on_day("Sun<=25", 1), Ok(OnDay::AtMost(Weekday::Sunday, 25)));
+        assert_eq!(parse_on_day("15", 1),      Ok(OnDay::Numbered(15)));
+    }
+
+    #[test]
+    fn parses_clock_suffixes() {
+        assert_eq!(parse_at("2:00", 1),  Ok(At { seconds: 7200, clock: Clock::Wall }));
+        assert_eq!(parse_at("2:00s", 1), Ok(At { seconds: 7200, clock: Clock::Standard }));
+        assert_eq!(parse_at("2:00u", 1), Ok(At { seconds: 7200, clock: Clock::Utc }));
+    }
+
+    #[test]
+    fn rejects_unknown_month() {
+        let err = parse_source("Rule US 1967 1973 - Foo lastSun 2:00 1:00 D").unwrap_err();
+        assert_eq!(err, TzdataError::UnknownMonth { line: 1, text: "Foo".to_string() });
+    }
+
+    #[test]
+    fn rejects_too_few_fields() {
+        let err = parse_source("Zone America/New_York -4:56:02").unwrap_err();
+        assert_eq!(err, TzdataError::TooFewFields { line: 1, kind: "Zone" });
+    }
+
+    #[test]
+    fn groups_a_zone_and_its_continuation_by_name() {
+        let db = parse(
+            "Zone America/New_York -4:56:02 -     LMT     1883 Nov 18 12:03:58
+                                   -5:00   US      E%sT    1920"
+        ).unwrap();
+
+        let lines = &db.zones["America/New_York"];
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].format, "LMT");
+        assert_eq!(lines[1].format, "E%sT");
+    }
+
+    #[test]
+    fn groups_rules_by_name() {
+        let db = parse(
+            "Rule    US    1967    1973    -    Apr    lastSun    2:00    1:00    D\n\
+             Rule    US    1967    2006    -    Oct    lastSun    2:00    0       S"
+        ).unwrap();
+
+        assert_eq!(db.rules["US"].len(), 2);
+        assert_eq!(db.rules["US"][1].letters, "S");
+    }
+
+    #[test]
+    fn offset_at_resolves_links() {
+        let db = parse(
+            "Zone America/New_York -5:00 US E%sT\n\
+             Link  America/New_York  US/Eastern"
+        ).unwrap();
+
+        assert_eq!(db.offset_at("US/Eastern", 0), Some(-5 * 3600));
+    }
+
+    #[test]
+    fn offset_at_unknown_zone_is_none() {
+        let db = parse("Zone America/New_York -5:00 US E%sT").unwrap();
+        assert_eq!(db.offset_at("Nowhere/At_All", 0), None);
+    }
+
+    #[test]
+    fn offset_at_respects_until_column() {
+        let db = parse(
+            "Zone Test/Zone -5:00 - EST 2000
+                           -6:00 - CST"
+        ).unwrap();
+
+        assert_eq!(db.offset_at("Test/Zone", -1_000_000_000), Some(-5 * 3600)); // 1938, before the UNTIL boundary
+        assert_eq!(db.offset_at("Test/Zone",  2_000_000_000), Some(-6 * 3600)); // 2033, after it
+    }
+
+    #[test]
+    fn offset_at_applies_named_rule_dst() {
+        let db = parse(
+            "Rule    US    1967    max    -    Apr    lastSun    2:00    1:00    D\n\
+             Rule    US    1967    max    -    Oct    lastSun    2:00    0       S\n\
+             Zone    Test/US    -5:00    US    E%sT"
+        ).unwrap();
+
+        assert_eq!(db.offset_at("Test/US", 1579046400), Some(-5 * 3600)); // 2020-01-15, standard time
+        assert_eq!(db.offset_at("Test/US", 1594771200), Some(-4 * 3600)); // 2020-07-15, daylight time
+    }
+}